@@ -1,18 +1,19 @@
 use anyhow::{anyhow, Result};
 use clap::{Parser, ValueEnum};
 use image::{DynamicImage, GenericImageView, ImageFormat};
+use rayon::prelude::*;
 use std::fs::File;
-use std::io::{BufWriter, Cursor, Read, Write};
+use std::io::{Cursor, Write};
 use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(name = "compress")]
 #[command(about = "Compress images to a target size constraint", long_about = None)]
 struct Args {
-    /// Input image file
+    /// Input image file, or a directory of images to compress in bulk
     input: PathBuf,
 
-    /// Output image file
+    /// Output image file, or the directory to write bulk results into
     output: PathBuf,
 
     /// Maximum size (e.g., 500KB, 1MB)
@@ -22,6 +23,37 @@ struct Args {
     /// Output format (optional, auto-detects from output extension if not provided)
     #[arg(long, value_enum, default_value_t = Format::Auto)]
     format: Format,
+
+    /// JPEG encoder backend to use
+    #[arg(long, value_enum, default_value_t = JpegEncoderKind::Mozjpeg)]
+    encoder: JpegEncoderKind,
+
+    /// oxipng optimization level (0 = fastest, 6 = smallest)
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(0..=6))]
+    png_level: u8,
+
+    /// Use the Zopfli deflater for PNG instead of zlib (much slower, smaller output)
+    #[arg(long, default_value_t = false)]
+    png_zopfli: bool,
+
+    /// Lowest JPEG/WebP/AVIF quality the iterative search is allowed to drop to
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(0..=100))]
+    min_quality: u32,
+
+    /// Lowest downscale percentage the iterative search is allowed to drop to
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=100))]
+    min_scale: u32,
+
+    /// Shorthand for `--min-quality 100 --min-scale 100`: never let the encoder
+    /// drop quality or downscale. Only PNG (via oxipng) is a true lossless
+    /// encode; JPEG/WebP/AVIF still run their ordinary lossy encoders, just
+    /// pinned to their highest quality setting.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["min_quality", "min_scale"])]
+    lossless: bool,
+
+    /// Exit with a non-zero status instead of a warning when the target size can't be met
+    #[arg(long, default_value_t = false)]
+    strict: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -30,115 +62,321 @@ enum Format {
     Jpeg,
     Png,
     Webp,
+    Avif,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum JpegEncoderKind {
+    /// `image` crate's built-in JPEG encoder
+    Image,
+    /// mozjpeg, for smaller files at equal visual quality
+    Mozjpeg,
 }
 
 fn get_file_size(path: &Path) -> Result<u64> {
     Ok(std::fs::metadata(path)?.len())
 }
 
-fn compress_jpeg(img: &DynamicImage, target_size: u64) -> Result<Vec<u8>> {
-    let mut quality = 90;
-    let mut scale = 1.0;
-    let mut buffer = Vec::new();
+/// Binary-search an integer parameter (quality, or scale expressed as a
+/// percentage) for the largest value in `[low, high]` whose encoding stays
+/// within `target_size`, calling `encode` at most
+/// `floor(log2(high - low + 1)) + 1` times (one more when nothing fits).
+/// If even `low` overshoots the target, returns `low`'s encoding anyway so
+/// callers can fall back to a second search (e.g. quality floor -> scale).
+fn binary_search_best<F>(low: u32, high: u32, target_size: u64, mut encode: F) -> Result<(u32, Vec<u8>)>
+where
+    F: FnMut(u32) -> Result<Vec<u8>>,
+{
+    let mut lo = low;
+    let mut hi = high;
+    let mut best: Option<(u32, Vec<u8>)> = None;
 
-    loop {
-        buffer.clear();
-        let mut cursor = Cursor::new(&mut buffer);
-        
-        // Use zune-jpeg for high performance encoding if possible or just image crate
-        let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut cursor, quality);
-        
-        let processed_img = if scale < 1.0 {
-            let (w, h) = img.dimensions();
-            img.resize(
-                (w as f32 * scale) as u32,
-                (h as f32 * scale) as u32,
-                image::imageops::FilterType::Lanczos3,
-            )
-        } else {
-            img.clone()
-        };
+    while lo <= hi {
+        let mid = lo + (hi - lo) / 2;
+        let buffer = encode(mid)?;
 
-        encoder.encode_image(&processed_img)?;
-        
-        if buffer.len() as u64 <= target_size || (quality <= 10 && scale <= 0.1) {
-            break;
+        if buffer.len() as u64 <= target_size {
+            if best.as_ref().is_none_or(|(q, _)| mid > *q) {
+                best = Some((mid, buffer));
+            }
+            if mid == high {
+                break;
+            }
+            lo = mid + 1;
+        } else {
+            if mid == low {
+                break;
+            }
+            hi = mid - 1;
         }
+    }
 
-        if quality > 10 {
-            quality -= 10;
-        } else {
-            scale -= 0.1;
+    match best {
+        Some(result) => Ok(result),
+        None => encode(low).map(|buffer| (low, buffer)),
+    }
+}
+
+
+fn compress_jpeg(
+    img: &DynamicImage,
+    target_size: u64,
+    encoder: JpegEncoderKind,
+    min_quality: u32,
+    min_scale: u32,
+) -> Result<Vec<u8>> {
+    let encode_at = |quality: u32| -> Result<Vec<u8>> {
+        match encoder {
+            JpegEncoderKind::Image => {
+                let mut buffer = Vec::new();
+                let mut cursor = Cursor::new(&mut buffer);
+                let mut encoder =
+                    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut cursor, quality as u8);
+                encoder.encode_image(img)?;
+                Ok(buffer)
+            }
+            JpegEncoderKind::Mozjpeg => encode_mozjpeg(img, quality as u8),
         }
+    };
+
+    let (_, buffer) = binary_search_best(min_quality, 100, target_size, encode_at)?;
+    if buffer.len() as u64 <= target_size || min_scale >= 100 {
+        return Ok(buffer);
     }
 
+    // Quality floor reached and still over budget: fall back to downscaling,
+    // re-encoding at the lowest allowed quality for each candidate scale.
+    let (w, h) = img.dimensions();
+    let (_, buffer) = binary_search_best(min_scale, 100, target_size, |scale_pct| {
+        let resized = img.resize(
+            (w as f32 * scale_pct as f32 / 100.0) as u32,
+            (h as f32 * scale_pct as f32 / 100.0) as u32,
+            image::imageops::FilterType::Lanczos3,
+        );
+        match encoder {
+            JpegEncoderKind::Image => {
+                let mut buffer = Vec::new();
+                let mut cursor = Cursor::new(&mut buffer);
+                let mut encoder =
+                    image::codecs::jpeg::JpegEncoder::new_with_quality(&mut cursor, min_quality as u8);
+                encoder.encode_image(&resized)?;
+                Ok(buffer)
+            }
+            JpegEncoderKind::Mozjpeg => encode_mozjpeg(&resized, min_quality as u8),
+        }
+    })?;
+
     Ok(buffer)
 }
 
-fn compress_png(img: &DynamicImage, target_size: u64) -> Result<Vec<u8>> {
-    let mut scale = 1.0;
-    
-    loop {
-        let mut buffer = Vec::new();
-        let mut cursor = Cursor::new(&mut buffer);
+/// Encode an image as JPEG via mozjpeg, which uses trellis quantization and
+/// optimized Huffman tables to produce smaller files than the `image` crate's
+/// encoder at the same visual quality.
+fn encode_mozjpeg(img: &DynamicImage, quality: u8) -> Result<Vec<u8>> {
+    let rgb = img.to_rgb8();
+    let (width, height) = rgb.dimensions();
+
+    let mut compress = mozjpeg::Compress::new(mozjpeg::ColorSpace::JCS_RGB);
+    compress.set_size(width as usize, height as usize);
+    compress.set_quality(quality as f32);
+    compress.set_progressive_mode();
+    compress.set_optimize_scans(true);
+    compress.set_use_scans_in_trellis(true);
+
+    let mut started = compress
+        .start_compress(Vec::new())
+        .map_err(|e| anyhow!("mozjpeg error: {}", e))?;
+    started
+        .write_scanlines(rgb.as_raw())
+        .map_err(|e| anyhow!("mozjpeg error: {}", e))?;
+    started
+        .finish()
+        .map_err(|e| anyhow!("mozjpeg error: {}", e))
+}
+
+fn compress_png(
+    img: &DynamicImage,
+    target_size: u64,
+    png_level: u8,
+    png_zopfli: bool,
+    min_scale: u32,
+) -> Result<Vec<u8>> {
+    let (w, h) = img.dimensions();
+
+    let mut options = oxipng::Options::from_preset(png_level);
+    if png_zopfli {
+        options.deflate = oxipng::Deflaters::Zopfli {
+            iterations: std::num::NonZeroU8::new(15).unwrap(),
+        };
+    }
 
-        let processed_img = if scale < 1.0 {
-            let (w, h) = img.dimensions();
+    let (_, buffer) = binary_search_best(min_scale, 100, target_size, |scale_pct| {
+        let processed_img = if scale_pct < 100 {
             img.resize(
-                (w as f32 * scale) as u32,
-                (h as f32 * scale) as u32,
+                (w as f32 * scale_pct as f32 / 100.0) as u32,
+                (h as f32 * scale_pct as f32 / 100.0) as u32,
                 image::imageops::FilterType::Lanczos3,
             )
         } else {
             img.clone()
         };
 
+        let mut buffer = Vec::new();
+        let mut cursor = Cursor::new(&mut buffer);
         processed_img.write_to(&mut cursor, ImageFormat::Png)?;
 
-        // Now optimize with oxipng
-        let options = oxipng::Options::from_preset(2); // Balanced preset
-        let optimized = oxipng::optimize_from_memory(&buffer, &options)
-            .map_err(|e| anyhow!("Oxipng error: {}", e))?;
+        oxipng::optimize_from_memory(&buffer, &options).map_err(|e| anyhow!("Oxipng error: {}", e))
+    })?;
 
-        if optimized.len() as u64 <= target_size || scale <= 0.1 {
-            return Ok(optimized);
-        }
+    Ok(buffer)
+}
 
-        scale -= 0.1;
+fn compress_webp(img: &DynamicImage, target_size: u64, min_quality: u32, min_scale: u32) -> Result<Vec<u8>> {
+    let encode_at = |quality: u32| -> Result<Vec<u8>> {
+        let rgba = img.to_rgba8();
+        let (w, h) = rgba.dimensions();
+        let encoder = webp::Encoder::from_rgba(&rgba, w, h);
+        Ok(encoder.encode(quality as f32).to_vec())
+    };
+
+    let (_, buffer) = binary_search_best(min_quality, 100, target_size, encode_at)?;
+    if buffer.len() as u64 <= target_size || min_scale >= 100 {
+        return Ok(buffer);
     }
+
+    // Quality floor reached and still over budget: fall back to downscaling
+    // at the lowest allowed quality for each candidate scale.
+    let (w, h) = img.dimensions();
+    let (_, buffer) = binary_search_best(min_scale, 100, target_size, |scale_pct| {
+        let resized = img.resize(
+            (w as f32 * scale_pct as f32 / 100.0) as u32,
+            (h as f32 * scale_pct as f32 / 100.0) as u32,
+            image::imageops::FilterType::Lanczos3,
+        );
+        let rgba = resized.to_rgba8();
+        let (rw, rh) = rgba.dimensions();
+        let encoder = webp::Encoder::from_rgba(&rgba, rw, rh);
+        Ok(encoder.encode(min_quality as f32).to_vec())
+    })?;
+
+    Ok(buffer)
 }
 
-fn compress_webp(img: &DynamicImage, target_size: u64) -> Result<Vec<u8>> {
-    let mut quality = 80.0;
-    let mut scale = 1.0;
-    
-    loop {
-        let mut buffer = Vec::new();
-        let mut cursor = Cursor::new(&mut buffer);
+/// Encode an image as AVIF via libavif (the aom codec), iterating the
+/// quality parameter toward `target_size` the same way `compress_jpeg` does.
+/// Unlike aom's raw quantizer, libavif's `Encoder::set_quality` already uses
+/// a 0 (worst) - 100 (best) scale, so `quality` maps onto it directly.
+fn compress_avif(img: &DynamicImage, target_size: u64, min_quality: u32, min_scale: u32) -> Result<Vec<u8>> {
+    let encode_at = |quality: u32| -> Result<Vec<u8>> {
+        let rgba = img.to_rgba8();
+        let (w, h) = rgba.dimensions();
 
-        let processed_img = if scale < 1.0 {
-            let (w, h) = img.dimensions();
-            img.resize(
-                (w as f32 * scale) as u32,
-                (h as f32 * scale) as u32,
-                image::imageops::FilterType::Lanczos3,
-            )
-        } else {
-            img.clone()
-        };
+        let mut encoder = libavif::Encoder::new();
+        encoder.set_quality(quality as u8);
+        encoder.set_speed(6);
+
+        let pixels = libavif::RgbPixels::new(w, h, &rgba)
+            .map_err(|e| anyhow!("libavif error: {}", e))?;
+        let image = pixels.to_image(libavif::YuvFormat::Yuv420);
+        let avif = encoder
+            .encode(&image)
+            .map_err(|e| anyhow!("libavif error: {}", e))?;
+        Ok(avif.to_vec())
+    };
+
+    let (_, buffer) = binary_search_best(min_quality, 100, target_size, encode_at)?;
+    if buffer.len() as u64 <= target_size || min_scale >= 100 {
+        return Ok(buffer);
+    }
+
+    // Quality floor reached and still over budget: fall back to downscaling
+    // at the lowest allowed quality for each candidate scale.
+    let (w, h) = img.dimensions();
+    let (_, buffer) = binary_search_best(min_scale, 100, target_size, |scale_pct| {
+        let resized = img.resize(
+            (w as f32 * scale_pct as f32 / 100.0) as u32,
+            (h as f32 * scale_pct as f32 / 100.0) as u32,
+            image::imageops::FilterType::Lanczos3,
+        );
+        let rgba = resized.to_rgba8();
+        let (rw, rh) = rgba.dimensions();
+
+        let mut encoder = libavif::Encoder::new();
+        encoder.set_quality(min_quality as u8);
+        encoder.set_speed(6);
+
+        let pixels = libavif::RgbPixels::new(rw, rh, &rgba)
+            .map_err(|e| anyhow!("libavif error: {}", e))?;
+        let image = pixels.to_image(libavif::YuvFormat::Yuv420);
+        let avif = encoder
+            .encode(&image)
+            .map_err(|e| anyhow!("libavif error: {}", e))?;
+        Ok(avif.to_vec())
+    })?;
+
+    Ok(buffer)
+}
+
+/// Detect the output format from `reference`'s extension when `format` is `Auto`.
+fn resolve_format(format: Format, reference: &Path) -> Format {
+    if format != Format::Auto {
+        return format;
+    }
+    match reference.extension().and_then(|s| s.to_str()) {
+        Some("jpg") | Some("jpeg") => Format::Jpeg,
+        Some("png") => Format::Png,
+        Some("webp") => Format::Webp,
+        Some("avif") => Format::Avif,
+        _ => Format::Jpeg, // Default to JPEG if unknown
+    }
+}
+
+/// The file extension a compressed image of `format` should be written with.
+fn extension_for(format: Format) -> &'static str {
+    match format {
+        Format::Jpeg | Format::Auto => "jpg",
+        Format::Png => "png",
+        Format::Webp => "webp",
+        Format::Avif => "avif",
+    }
+}
+
+/// Per-file outcome of [`process_one`]: (original size, final size, met target).
+type CompressResult = Result<(u64, u64, bool)>;
+
+struct CompressOptions {
+    format: Format,
+    encoder: JpegEncoderKind,
+    png_level: u8,
+    png_zopfli: bool,
+    min_quality: u32,
+    min_scale: u32,
+}
+
+/// Compress a single image file to `output`, returning (original size, final
+/// size, whether the target size was met).
+fn process_one(input: &Path, output: &Path, target_size: u64, opts: &CompressOptions) -> CompressResult {
+    let original_size = get_file_size(input)?;
+    let img = image::open(input)?;
+    let format = resolve_format(opts.format, output);
 
-        // WebP encoding via image crate
-        processed_img.write_to(&mut cursor, ImageFormat::WebP)?;
-        // Note: image crate's webp doesn't expose quality easily in a generic write_to. 
-        // We might need a specific encoder if we want iterative quality reduction for WebP.
-        // For now, let's focus on scale for WebP if quality isn't easily reachable via image crate.
-        
-        if buffer.len() as u64 <= target_size || scale <= 0.1 {
-            return Ok(buffer);
+    let compressed_data = match format {
+        Format::Jpeg | Format::Auto => {
+            compress_jpeg(&img, target_size, opts.encoder, opts.min_quality, opts.min_scale)?
         }
+        Format::Png => compress_png(&img, target_size, opts.png_level, opts.png_zopfli, opts.min_scale)?,
+        Format::Webp => compress_webp(&img, target_size, opts.min_quality, opts.min_scale)?,
+        Format::Avif => compress_avif(&img, target_size, opts.min_quality, opts.min_scale)?,
+    };
 
-        scale -= 0.1;
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
     }
+    let mut out_file = File::create(output)?;
+    out_file.write_all(&compressed_data)?;
+
+    let final_size = get_file_size(output)?;
+    Ok((original_size, final_size, final_size <= target_size))
 }
 
 fn main() -> Result<()> {
@@ -149,37 +387,156 @@ fn main() -> Result<()> {
 
     println!("Target size: {} bytes", target_size);
 
-    // Detect format
-    let img = image::open(&args.input)?;
-    let format = if args.format == Format::Auto {
-        match args.output.extension().and_then(|s| s.to_str()) {
-            Some("jpg") | Some("jpeg") => Format::Jpeg,
-            Some("png") => Format::Png,
-            Some("webp") => Format::Webp,
-            _ => Format::Jpeg, // Default to JPEG if unknown
-        }
-    } else {
-        args.format
+    let (min_quality, min_scale) = if args.lossless { (100, 100) } else { (args.min_quality, args.min_scale) };
+    let opts = CompressOptions {
+        format: args.format,
+        encoder: args.encoder,
+        png_level: args.png_level,
+        png_zopfli: args.png_zopfli,
+        min_quality,
+        min_scale,
     };
 
-    println!("Identified format: {:?}", format);
+    let mut any_unmet = false;
 
-    let compressed_data = match format {
-        Format::Jpeg | Format::Auto => compress_jpeg(&img, target_size)?,
-        Format::Png => compress_png(&img, target_size)?,
-        Format::Webp => compress_webp(&img, target_size)?,
-    };
+    if args.input.is_dir() {
+        std::fs::create_dir_all(&args.output)?;
 
-    let mut out_file = File::create(&args.output)?;
-    out_file.write_all(&compressed_data)?;
+        let entries: Vec<PathBuf> = std::fs::read_dir(&args.input)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
 
-    let final_size = get_file_size(&args.output)?;
-    println!("Final size: {} bytes", final_size);
-    if final_size > target_size {
-        println!("Warning: Could not meet target size constraint within quality limits.");
+        let results: Vec<(PathBuf, CompressResult)> = entries
+            .par_iter()
+            .map(|input_path| {
+                // Resolve per-file instead of copying the input's extension, so an
+                // explicit `--format` (e.g. converting a folder of .jpg to WebP)
+                // produces correctly-named output files.
+                let resolved_format = resolve_format(opts.format, input_path);
+                let stem = input_path.file_stem().unwrap_or_default();
+                let output_path = args
+                    .output
+                    .join(stem)
+                    .with_extension(extension_for(resolved_format));
+                let result = process_one(input_path, &output_path, target_size, &opts);
+                (input_path.clone(), result)
+            })
+            .collect();
+
+        println!("\n{:<40} {:>12} {:>12}  met target", "file", "original", "final");
+        for (path, result) in &results {
+            match result {
+                Ok((original_size, final_size, met)) => {
+                    println!(
+                        "{:<40} {:>12} {:>12}  {}",
+                        path.display(),
+                        original_size,
+                        final_size,
+                        if *met { "yes" } else { "no" }
+                    );
+                    any_unmet |= !met;
+                }
+                Err(e) => println!("{:<40} failed: {}", path.display(), e),
+            }
+        }
+
+        let failures = results.iter().filter(|(_, r)| r.is_err()).count();
+        if failures > 0 {
+            return Err(anyhow!("{} file(s) failed to compress", failures));
+        }
     } else {
-        println!("Success: Image compressed within target size.");
+        let (_, final_size, met) = process_one(&args.input, &args.output, target_size, &opts)?;
+
+        println!("Final size: {} bytes", final_size);
+        if met {
+            println!("Success: Image compressed within target size.");
+        } else if !args.strict {
+            println!("Warning: Could not meet target size constraint within quality limits.");
+        }
+        any_unmet = !met;
+    }
+
+    if args.strict && any_unmet {
+        return Err(anyhow!(
+            "target size not met within the allowed quality/scale bounds (--strict)"
+        ));
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod binary_search_best_tests {
+    use super::*;
+
+    /// `encode` that is monotonic: larger `n` always produces a larger buffer.
+    fn monotonic(n: u32) -> Result<Vec<u8>> {
+        Ok(vec![0u8; n as usize])
+    }
+
+    #[test]
+    fn target_below_minimum_achievable() {
+        // Even `low`'s encoding (size 10) overshoots a target of 5, so there's
+        // no value in range that fits; falls back to `low`'s own encoding.
+        let (best, buffer) = binary_search_best(10, 100, 5, monotonic).unwrap();
+        assert_eq!(best, 10);
+        assert_eq!(buffer.len(), 10);
+    }
+
+    #[test]
+    fn target_above_maximum_achievable() {
+        // `high`'s encoding (size 100) already fits comfortably, so the
+        // search should climb all the way to `high`.
+        let (best, buffer) = binary_search_best(10, 100, 1000, monotonic).unwrap();
+        assert_eq!(best, 100);
+        assert_eq!(buffer.len(), 100);
+    }
+
+    #[test]
+    fn monotonic_encode_finds_largest_fit() {
+        let (best, buffer) = binary_search_best(10, 100, 50, monotonic).unwrap();
+        assert_eq!(best, 50);
+        assert_eq!(buffer.len(), 50);
+    }
+
+    #[test]
+    fn non_monotonic_encode_keeps_best_fit_seen() {
+        // Odd values spike to an oversized buffer, breaking monotonicity.
+        // The search isn't guaranteed to find the true largest fitting `n`
+        // here (it still halves the range as if the function were
+        // monotonic), but it must never let a later, smaller fitting `mid`
+        // overwrite a larger one already recorded as `best`.
+        fn spiky(n: u32) -> Result<Vec<u8>> {
+            if n.is_multiple_of(2) {
+                Ok(vec![0u8; n as usize])
+            } else {
+                Ok(vec![0u8; 1000])
+            }
+        }
+
+        let (best, buffer) = binary_search_best(10, 100, 60, spiky).unwrap();
+        assert_eq!(best, 34);
+        assert_eq!(buffer.len(), 34);
+    }
+
+    #[test]
+    fn mid_equals_high_breaks_loop() {
+        // low == high means the first `mid` computed equals both `low` and
+        // `high`; the `mid == high` break must fire instead of looping.
+        let (best, buffer) = binary_search_best(42, 42, 1000, monotonic).unwrap();
+        assert_eq!(best, 42);
+        assert_eq!(buffer.len(), 42);
+    }
+
+    #[test]
+    fn mid_equals_low_breaks_loop() {
+        // In [1, 2] the first `mid` is `low` itself, and a target of 0 makes
+        // it overshoot, so the `mid == low` break on the failing branch fires
+        // and the search falls back to `low`'s own encoding.
+        let (best, buffer) = binary_search_best(1, 2, 0, monotonic).unwrap();
+        assert_eq!(best, 1);
+        assert_eq!(buffer.len(), 1);
+    }
+}